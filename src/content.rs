@@ -2,6 +2,13 @@
 
 use mime::Mime;
 
+use error::CgiResult;
+#[cfg(feature = "serde")]
+use error::Error;
+use multipart::{self, Part};
+#[cfg(feature = "serde")]
+use serde::de::DeserializeOwned;
+
 /// Processed content from PUT and POST requests.
 ///
 /// This will automatically store posted content in its most sensible form based on its MIME type.
@@ -20,8 +27,89 @@ enum Data {
     Xml(String),
     /// Other plaintext data.
     Text(String),
-    /// Multipart encoded data.
-    Multipart(Vec<Data>),
+    /// Multipart encoded data, as the named parts it was made up of.
+    Multipart(Vec<Part>),
     /// Binary data stored in memory.
     Blob(Vec<u8>),
 }
+
+impl Content {
+    /// Build content from raw bytes, choosing the in-memory representation from the MIME type.
+    pub(crate) fn from_parts(mime: Mime, bytes: Vec<u8>) -> CgiResult<Content> {
+        let data = match mime.essence_str() {
+            "application/x-www-form-urlencoded" => Data::Form(String::from_utf8(bytes)?),
+            "application/json" => Data::Json(String::from_utf8(bytes)?),
+            "application/xml" | "text/xml" => Data::Xml(String::from_utf8(bytes)?),
+            "multipart/form-data" => Data::Multipart(multipart::parse(&mime, bytes)?),
+            _ if mime.type_() == ::mime::TEXT => Data::Text(String::from_utf8(bytes)?),
+            _ => Data::Blob(bytes),
+        };
+
+        Ok(Content { mime, data })
+    }
+
+    /// The MIME type the content was stored under.
+    pub fn mime(&self) -> &Mime {
+        &self.mime
+    }
+
+    /// Serialise the content into bytes, for writing into a response body.
+    ///
+    /// Multipart content yields its parts' bodies concatenated; the boundaries and part headers
+    /// are not written.
+    pub fn into_bytes(self) -> Vec<u8> {
+        match self.data {
+            Data::Form(text) | Data::Json(text) | Data::Xml(text) | Data::Text(text) => {
+                text.into_bytes()
+            }
+            Data::Multipart(parts) => {
+                parts.into_iter().flat_map(Part::into_bytes).collect()
+            }
+            Data::Blob(bytes) => bytes,
+        }
+    }
+
+    /// Get an uploaded multipart field by its form name.
+    ///
+    /// Returns `None` if the content is not multipart, or has no part with that name.
+    pub fn field(&self, name: &str) -> Option<&Part> {
+        self.parts().iter().find(|part| part.name() == name)
+    }
+
+    /// Iterate over the multipart parts that carry an uploaded filename.
+    pub fn files(&self) -> impl Iterator<Item = &Part> {
+        self.parts().iter().filter(|part| part.filename().is_some())
+    }
+
+    fn parts(&self) -> &[Part] {
+        match self.data {
+            Data::Multipart(ref parts) => parts,
+            _ => &[],
+        }
+    }
+
+    /// Deserialize JSON content into `T`.
+    ///
+    /// Fails with `Error::WrongContentType` if the content was not stored as JSON.
+    #[cfg(feature = "serde")]
+    pub fn json<T: DeserializeOwned>(&self) -> CgiResult<T> {
+        match self.data {
+            Data::Json(ref text) => {
+                ::serde_json::from_str(text).map_err(|err| Error::Deserialize(err.to_string()))
+            }
+            _ => Err(Error::WrongContentType),
+        }
+    }
+
+    /// Deserialize url-encoded form content into `T`.
+    ///
+    /// Fails with `Error::WrongContentType` if the content was not stored as form data.
+    #[cfg(feature = "serde")]
+    pub fn form<T: DeserializeOwned>(&self) -> CgiResult<T> {
+        match self.data {
+            Data::Form(ref text) => ::serde_urlencoded::from_str(text)
+                .map_err(|err| Error::Deserialize(err.to_string())),
+            _ => Err(Error::WrongContentType),
+        }
+    }
+}
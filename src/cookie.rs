@@ -0,0 +1,154 @@
+//! Cookie parsing for requests (`HTTP_COOKIE`) and emission for responses (`Set-Cookie`).
+
+use std::collections::HashMap;
+
+/// The cookies sent with a request, parsed from `HTTP_COOKIE`.
+pub struct CookieJar {
+    cookies: HashMap<String, String>,
+}
+
+impl CookieJar {
+    /// An empty jar, for requests with no `HTTP_COOKIE` header.
+    pub(crate) fn new() -> CookieJar {
+        CookieJar { cookies: HashMap::new() }
+    }
+
+    /// Parse a `HTTP_COOKIE` header value into a jar.
+    pub(crate) fn parse(header: &str) -> CookieJar {
+        let mut cookies = HashMap::new();
+        for pair in header.split(';') {
+            let mut parts = pair.trim().splitn(2, '=');
+            if let (Some(name), Some(value)) = (parts.next(), parts.next()) {
+                cookies.insert(name.trim().to_string(), value.trim().to_string());
+            }
+        }
+        CookieJar { cookies }
+    }
+
+    /// Set a cookie, overwriting any existing value with the same name.
+    pub(crate) fn set(&mut self, name: &str, value: &str) {
+        self.cookies.insert(name.to_string(), value.to_string());
+    }
+
+    /// Get a cookie's value by name.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.cookies.get(name).map(String::as_str)
+    }
+
+    /// Iterate over all cookies as `(name, value)` pairs.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.cookies.iter().map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+}
+
+/// The `SameSite` attribute of a `Set-Cookie` header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SameSite {
+    Strict,
+    Lax,
+    None,
+}
+
+impl SameSite {
+    fn as_str(self) -> &'static str {
+        match self {
+            SameSite::Strict => "Strict",
+            SameSite::Lax => "Lax",
+            SameSite::None => "None",
+        }
+    }
+}
+
+/// A `Set-Cookie` response header being built.
+///
+/// Mirrors the fluent `Builder` used to construct test `Request`s.
+pub struct SetCookie {
+    name: String,
+    value: String,
+    expires: Option<String>,
+    path: Option<String>,
+    domain: Option<String>,
+    secure: bool,
+    http_only: bool,
+    same_site: Option<SameSite>,
+}
+
+impl SetCookie {
+    /// Start building a cookie with a name and value.
+    pub fn new(name: &str, value: &str) -> SetCookie {
+        SetCookie {
+            name: name.to_string(),
+            value: value.to_string(),
+            expires: None,
+            path: None,
+            domain: None,
+            secure: false,
+            http_only: false,
+            same_site: None,
+        }
+    }
+
+    /// Set the `Expires` attribute to an HTTP-date.
+    pub fn expires(mut self, http_date: &str) -> SetCookie {
+        self.expires = Some(http_date.to_string());
+        self
+    }
+
+    /// Set the `Path` attribute.
+    pub fn path(mut self, path: &str) -> SetCookie {
+        self.path = Some(path.to_string());
+        self
+    }
+
+    /// Set the `Domain` attribute.
+    pub fn domain(mut self, domain: &str) -> SetCookie {
+        self.domain = Some(domain.to_string());
+        self
+    }
+
+    /// Set the `Secure` attribute.
+    pub fn secure(mut self) -> SetCookie {
+        self.secure = true;
+        self
+    }
+
+    /// Set the `HttpOnly` attribute.
+    pub fn http_only(mut self) -> SetCookie {
+        self.http_only = true;
+        self
+    }
+
+    /// Set the `SameSite` attribute.
+    pub fn same_site(mut self, same_site: SameSite) -> SetCookie {
+        self.same_site = Some(same_site);
+        self
+    }
+
+    /// Render the `Set-Cookie` header value.
+    pub(crate) fn into_header_value(self) -> String {
+        let mut header = format!("{}={}", self.name, self.value);
+        if let Some(ref expires) = self.expires {
+            header.push_str("; Expires=");
+            header.push_str(expires);
+        }
+        if let Some(ref path) = self.path {
+            header.push_str("; Path=");
+            header.push_str(path);
+        }
+        if let Some(ref domain) = self.domain {
+            header.push_str("; Domain=");
+            header.push_str(domain);
+        }
+        if self.secure {
+            header.push_str("; Secure");
+        }
+        if self.http_only {
+            header.push_str("; HttpOnly");
+        }
+        if let Some(same_site) = self.same_site {
+            header.push_str("; SameSite=");
+            header.push_str(same_site.as_str());
+        }
+        header
+    }
+}
@@ -1,7 +1,8 @@
 //! Errors for the CGI crate.
 
-use std::{error, fmt};
+use std::{error, fmt, io, string};
 use std::convert::From;
+use mime::FromStrError;
 use url::ParseError;
 
 /// Results involding errors related to cgi handling and testing.
@@ -14,19 +15,46 @@ pub enum Error {
     MultipleLoad,
     /// Could not form a legitimate url.
     UrlParse(ParseError),
+    /// Could not read the request from the environment.
+    Io(io::Error),
+    /// Could not parse a MIME type (e.g. `CONTENT_TYPE`).
+    MimeParse(FromStrError),
+    /// Content was not valid UTF-8 for its declared MIME type.
+    Encoding(string::FromUtf8Error),
+    /// A `multipart/form-data` body was malformed.
+    Multipart(String),
+    /// Could not deserialize content into the requested type.
+    #[cfg(feature = "serde")]
+    Deserialize(String),
+    /// The content was not stored in a representation that this operation can read.
+    #[cfg(feature = "serde")]
+    WrongContentType,
 }
 
 impl error::Error for Error {
     fn description(&self) -> &str {
         match *self {
             Error::MultipleLoad => "Multiple attempts were made to load the request.",
-            Error::UrlParse(_) => "Tried to make the URL invalid."
+            Error::UrlParse(_) => "Tried to make the URL invalid.",
+            Error::Io(_) => "Could not read the request from the environment.",
+            Error::MimeParse(_) => "Could not parse a MIME type.",
+            Error::Encoding(_) => "Content was not valid UTF-8 for its declared MIME type.",
+            Error::Multipart(_) => "A multipart/form-data body was malformed.",
+            #[cfg(feature = "serde")]
+            Error::Deserialize(_) => "Could not deserialize content into the requested type.",
+            #[cfg(feature = "serde")]
+            Error::WrongContentType => {
+                "The content was not stored in a representation that this operation can read."
+            }
         }
     }
 
     fn cause(&self) -> Option<&error::Error> {
         match *self {
             Error::UrlParse(ref err) => Some(err),
+            Error::Io(ref err) => Some(err),
+            Error::MimeParse(ref err) => Some(err),
+            Error::Encoding(ref err) => Some(err),
             _ => None,
         }
     }
@@ -36,6 +64,10 @@ impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
         match *self {
             Error::UrlParse(ref err) => write!(f, "Tried to make url invalid: {}", err),
+            Error::Io(ref err) => write!(f, "Could not read request: {}", err),
+            Error::MimeParse(ref err) => write!(f, "Could not parse MIME type: {}", err),
+            Error::Encoding(ref err) => write!(f, "Could not decode content: {}", err),
+            Error::Multipart(ref reason) => write!(f, "Malformed multipart body: {}", reason),
             _ => write!(f, "{}", error::Error::description(self))
         }
     }
@@ -46,3 +78,21 @@ impl From<ParseError> for Error {
         Error::UrlParse(err)
     }
 }
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Error {
+        Error::Io(err)
+    }
+}
+
+impl From<FromStrError> for Error {
+    fn from(err: FromStrError) -> Error {
+        Error::MimeParse(err)
+    }
+}
+
+impl From<string::FromUtf8Error> for Error {
+    fn from(err: string::FromUtf8Error) -> Error {
+        Error::Encoding(err)
+    }
+}
@@ -4,12 +4,25 @@
 
 extern crate mime;
 extern crate url;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(feature = "serde")]
+extern crate serde_json;
+#[cfg(feature = "serde")]
+extern crate serde_urlencoded;
 
 mod content;
+mod cookie;
 mod error;
+mod multipart;
+mod response;
 
 pub use content::*;
+pub use cookie::{CookieJar, SameSite, SetCookie};
 pub use error::*;
+pub use multipart::Part;
+pub use response::*;
+use mime::Mime;
 use std::net;
 use std::sync::atomic::{AtomicBool, Ordering};
 use url::Url;
@@ -51,9 +64,11 @@ pub struct Request {
     /// Authentication type used (`AUTH_TYPE`).
     auth: Option<String>,
     /// Client connection information (`REMOTE_HOST`).
-    client: net::Ipv4Addr,
+    client: net::IpAddr,
     /// Processed content from POST or PUT,
     content: Option<Content>,
+    /// Cookies sent with the request (`HTTP_COOKIE`).
+    cookies: CookieJar,
 }
 
 impl Request {
@@ -64,14 +79,89 @@ impl Request {
     ///
     /// Will return errors if more than one attempt is made to load a request.
     pub fn load() -> Result<Request, Error> {
+        use std::env;
+        use std::io::{self, Read};
 
         /// Only load once.
-        static request_loaded: AtomicBool = AtomicBool::new(false);
-        if request_loaded.swap(true, Ordering::Acquire) {
+        static REQUEST_LOADED: AtomicBool = AtomicBool::new(false);
+        if REQUEST_LOADED.swap(true, Ordering::Acquire) {
             return Err(Error::MultipleLoad);
         }
 
-        unimplemented!()
+        let var = |name: &str| env::var(name).ok();
+
+        // Compose the base of the url from the scheme, host and port; the path is filled
+        // in by `update_path` and the query from `QUERY_STRING` once the request is built.
+        let scheme = match var("HTTPS").as_ref().map(String::as_str) {
+            Some("on") | Some("ON") | Some("1") => "https",
+            _ => "http",
+        };
+        let host = var("SERVER_NAME").unwrap_or_else(|| "localhost".to_string());
+        let mut full_url = Url::parse(&format!("{}://{}", scheme, host))?;
+        if let Some(port) = var("SERVER_PORT").and_then(|port| port.parse().ok()) {
+            let _ = full_url.set_port(Some(port));
+        }
+
+        let method = match var("REQUEST_METHOD").as_ref().map(String::as_str) {
+            Some("HEAD") => Method::Head,
+            Some("POST") => Method::Post,
+            Some("PUT") => Method::Put,
+            Some("DELETE") => Method::Delete,
+            Some("OPTIONS") => Method::Options,
+            Some("TRACE") => Method::Trace,
+            Some("CONNECT") => Method::Connect,
+            _ => Method::Get,
+        };
+
+        let content = if method == Method::Post || method == Method::Put {
+            let length: usize = var("CONTENT_LENGTH")
+                .and_then(|length| length.parse().ok())
+                .unwrap_or(0);
+            let mut body = vec![0; length];
+            io::stdin().read_exact(&mut body).map_err(Error::Io)?;
+
+            let content_type = var("CONTENT_TYPE")
+                .unwrap_or_else(|| "application/octet-stream".to_string());
+            let mime: Mime = content_type.parse()?;
+            Some(Content::from_parts(mime, body)?)
+        } else {
+            None
+        };
+
+        // Prefer the client address forwarded by a reverse proxy, falling back to the
+        // connection's own `REMOTE_ADDR` if there is no (valid) forwarded address.
+        let forwarded = var("HTTP_X_FORWARDED_FOR")
+            .and_then(|header| header.split(',').next().and_then(|addr| addr.trim().parse().ok()));
+        let client = forwarded
+            .or_else(|| var("REMOTE_ADDR").and_then(|addr| addr.parse().ok()))
+            .unwrap_or(net::IpAddr::V4(net::Ipv4Addr::new(127, 0, 0, 1)));
+
+        let mut request = Request {
+            http_version: var("SERVER_PROTOCOL").unwrap_or_else(|| "HTTP/1.0".to_string()),
+            cgi_version: var("GATEWAY_INTERFACE").unwrap_or_else(|| "CGI/1.1".to_string()),
+            method,
+            full_url,
+            path_info: var("PATH_INFO"),
+            path_translated: var("PATH_TRANSLATED"),
+            script: var("SCRIPT_NAME").unwrap_or_default(),
+            user: var("REMOTE_USER"),
+            ident: var("REMOTE_IDENT"),
+            auth: var("AUTH_TYPE"),
+            client,
+            content,
+            cookies: var("HTTP_COOKIE")
+                .map(|header| CookieJar::parse(&header))
+                .unwrap_or_else(CookieJar::new),
+        };
+
+        request.update_path();
+        if let Some(query) = var("QUERY_STRING") {
+            if !query.is_empty() {
+                request.full_url.set_query(Some(&query));
+            }
+        }
+
+        Ok(request)
     }
 
     /// Get the url for a request.
@@ -79,6 +169,23 @@ impl Request {
         &self.full_url
     }
 
+    /// Deserialize the request's query string into `T`.
+    #[cfg(feature = "serde")]
+    pub fn query<T: ::serde::de::DeserializeOwned>(&self) -> CgiResult<T> {
+        let query = self.full_url.query().unwrap_or("");
+        ::serde_urlencoded::from_str(query).map_err(|err| Error::Deserialize(err.to_string()))
+    }
+
+    /// Get a cookie sent with the request by name.
+    pub fn cookie(&self, name: &str) -> Option<&str> {
+        self.cookies.get(name)
+    }
+
+    /// Iterate over all cookies sent with the request.
+    pub fn cookies(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.cookies.iter()
+    }
+
     /// Update the composite path in the URL.
     fn update_path(&mut self) {
         let mut composite_path = self.script.to_string();
@@ -104,8 +211,9 @@ impl Default for Request {
             user: None,
             ident: None,
             auth: None,
-            client: net::Ipv4Addr::new(127, 0, 0, 1),
+            client: net::IpAddr::V4(net::Ipv4Addr::new(127, 0, 0, 1)),
             content: None,
+            cookies: CookieJar::new(),
         }
     }
 }
@@ -223,7 +331,7 @@ impl Builder {
     }
 
     /// Set the client's IP address.
-    pub fn client(mut self, client: net::Ipv4Addr) -> Builder {
+    pub fn client(mut self, client: net::IpAddr) -> Builder {
         self.request.client = client;
         self
     }
@@ -233,6 +341,12 @@ impl Builder {
         self.request.full_url.set_query(Some(query));
         self
     }
+
+    /// Set a cookie as if it had been sent in `HTTP_COOKIE`.
+    pub fn cookie(mut self, name: &str, value: &str) -> Builder {
+        self.request.cookies.set(name, value);
+        self
+    }
 }
 
 /// Get the file URL for the running binary.
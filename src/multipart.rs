@@ -0,0 +1,136 @@
+//! Parsing of `multipart/form-data` bodies into their named parts.
+
+use mime::Mime;
+
+use content::Content;
+use error::{CgiResult, Error};
+
+/// A single part of a `multipart/form-data` body.
+pub struct Part {
+    name: String,
+    filename: Option<String>,
+    content: Content,
+}
+
+impl Part {
+    /// The form field name (`Content-Disposition: form-data; name="..."`).
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The uploaded filename, if this part represents a file.
+    pub fn filename(&self) -> Option<&str> {
+        self.filename.as_ref().map(String::as_str)
+    }
+
+    /// The part's own content.
+    pub fn content(&self) -> &Content {
+        &self.content
+    }
+
+    /// Consume the part, keeping only its raw content bytes.
+    pub(crate) fn into_bytes(self) -> Vec<u8> {
+        self.content.into_bytes()
+    }
+}
+
+/// Parse a `multipart/form-data` body into its named parts.
+pub(crate) fn parse(mime: &Mime, body: Vec<u8>) -> CgiResult<Vec<Part>> {
+    let boundary = mime
+        .get_param("boundary")
+        .ok_or_else(|| Error::Multipart("no boundary parameter on Content-Type".to_string()))?;
+    let delimiter = format!("--{}", boundary.as_str());
+    let delimiter = delimiter.as_bytes();
+
+    let mut rest = match find(&body, delimiter) {
+        Some(start) => &body[start + delimiter.len()..],
+        None => return Err(Error::Multipart("body contains no boundary".to_string())),
+    };
+
+    let mut parts = Vec::new();
+    loop {
+        // The final boundary is followed by `--` rather than a CRLF.
+        if rest.starts_with(b"--") {
+            break;
+        }
+        rest = skip_newline(rest);
+
+        let end = find(rest, delimiter)
+            .ok_or_else(|| Error::Multipart("unterminated multipart body".to_string()))?;
+        let (raw_part, remainder) = rest.split_at(end);
+        parts.push(parse_part(raw_part)?);
+        rest = &remainder[delimiter.len()..];
+    }
+
+    Ok(parts)
+}
+
+/// Parse a single part's headers and body, separated by a blank line.
+fn parse_part(raw: &[u8]) -> CgiResult<Part> {
+    let header_end = find(raw, b"\r\n\r\n")
+        .ok_or_else(|| Error::Multipart("part has no header/body separator".to_string()))?;
+
+    let mut body = raw[header_end + 4..].to_vec();
+    if body.ends_with(b"\r\n") {
+        let new_len = body.len() - 2;
+        body.truncate(new_len);
+    }
+
+    let mut name = None;
+    let mut filename = None;
+    let mut content_type = ::mime::TEXT_PLAIN;
+
+    for line in String::from_utf8_lossy(&raw[..header_end]).split("\r\n") {
+        let mut header = line.splitn(2, ':');
+        let header_name = header.next().unwrap_or("").trim();
+        let header_value = header.next().unwrap_or("").trim();
+
+        if header_name.eq_ignore_ascii_case("Content-Disposition") {
+            name = find_param(header_value, "name");
+            filename = find_param(header_value, "filename");
+        } else if header_name.eq_ignore_ascii_case("Content-Type") {
+            content_type = header_value.parse().unwrap_or(::mime::TEXT_PLAIN);
+        }
+    }
+
+    let name = name.ok_or_else(|| Error::Multipart("part has no name".to_string()))?;
+    let content = Content::from_parts(content_type, body)?;
+
+    Ok(Part { name, filename, content })
+}
+
+/// Find a `key="value"` (or unquoted `key=value`) parameter in a header value.
+fn find_param(header_value: &str, key: &str) -> Option<String> {
+    header_value
+        .split(';')
+        .filter_map(|segment| {
+            let mut pair = segment.trim().splitn(2, '=');
+            let param_key = pair.next()?.trim();
+            let param_value = pair.next()?.trim().trim_matches('"');
+            if param_key.eq_ignore_ascii_case(key) {
+                Some(param_value.to_string())
+            } else {
+                None
+            }
+        })
+        .next()
+}
+
+/// Skip a single leading CRLF (or LF) from `body`.
+fn skip_newline(body: &[u8]) -> &[u8] {
+    if body.starts_with(b"\r\n") {
+        &body[2..]
+    } else if body.starts_with(b"\n") {
+        &body[1..]
+    } else {
+        body
+    }
+}
+
+/// Find the first occurrence of `needle` in `haystack`.
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    if needle.is_empty() || haystack.len() < needle.len() {
+        return None;
+    }
+    haystack.windows(needle.len()).position(|window| window == needle)
+}
@@ -0,0 +1,186 @@
+//! Response construction and emission for CGI programs.
+//!
+//! `ResponseBuilder` mirrors the fluent `Builder` used to construct test `Request`s: accumulate a
+//! status, headers and a body, then `send` it to write the correct CGI response to stdout.
+
+use std::io::{self, Write};
+
+use mime::{self, Mime};
+
+use content::Content;
+use cookie::SetCookie;
+use error::CgiResult;
+
+/// An HTTP status code and its reason phrase, as sent in the CGI `Status:` header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Status(u16, &'static str);
+
+impl Status {
+    /// `200 OK`.
+    pub const OK: Status = Status(200, "OK");
+    /// `302 Found`, used for client redirects.
+    pub const FOUND: Status = Status(302, "Found");
+    /// `404 Not Found`.
+    pub const NOT_FOUND: Status = Status(404, "Not Found");
+
+    /// Build a status from an arbitrary code and reason phrase.
+    pub fn new(code: u16, reason: &'static str) -> Status {
+        Status(code, reason)
+    }
+}
+
+/// A response body together with the content type it should be sent as.
+///
+/// Built via `From` so that `ResponseBuilder::body` can accept `&str`, `Vec<u8>`, or `Content`.
+pub struct Body {
+    content_type: Option<Mime>,
+    data: Vec<u8>,
+}
+
+impl<'a> From<&'a str> for Body {
+    fn from(body: &'a str) -> Body {
+        Body { content_type: Some(mime::TEXT_PLAIN_UTF_8), data: body.as_bytes().to_vec() }
+    }
+}
+
+impl From<Vec<u8>> for Body {
+    fn from(body: Vec<u8>) -> Body {
+        Body { content_type: Some(mime::APPLICATION_OCTET_STREAM), data: body }
+    }
+}
+
+impl From<Content> for Body {
+    fn from(content: Content) -> Body {
+        let content_type = content.mime().clone();
+        Body { content_type: Some(content_type), data: content.into_bytes() }
+    }
+}
+
+/// A CGI response, either a document with a status and body, or a redirect.
+pub struct Response {
+    status: Option<Status>,
+    location: Option<String>,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl Default for Response {
+    fn default() -> Response {
+        Response {
+            status: Some(Status::OK),
+            location: None,
+            headers: Vec::new(),
+            body: Vec::new(),
+        }
+    }
+}
+
+impl Response {
+    /// Start building a `200 OK` response.
+    pub fn ok() -> ResponseBuilder {
+        ResponseBuilder::new()
+    }
+
+    /// Start building a `404 Not Found` response.
+    pub fn not_found() -> ResponseBuilder {
+        ResponseBuilder::new().status(Status::NOT_FOUND)
+    }
+
+    /// Start building a redirect to `location`.
+    ///
+    /// A location without `://` is sent as a CGI local redirect (a `Location:` header with no
+    /// `Status:`); anything else is sent as a client redirect (`Location:` plus a `302 Found`
+    /// status). Both are followed by any headers set on the builder and `Content-Length`.
+    pub fn redirect(location: &str) -> ResponseBuilder {
+        ResponseBuilder::new().redirect(location)
+    }
+
+    /// Write the CGI response to stdout.
+    pub fn send(&self) -> CgiResult<()> {
+        self.write_to(&mut io::stdout())
+    }
+
+    fn write_to<W: Write>(&self, out: &mut W) -> CgiResult<()> {
+        if let Some(ref location) = self.location {
+            write!(out, "Location: {}\r\n", location)?;
+            if let Some(status) = self.status {
+                write!(out, "Status: {} {}\r\n", status.0, status.1)?;
+            }
+        } else {
+            let status = self.status.unwrap_or(Status::OK);
+            write!(out, "Status: {} {}\r\n", status.0, status.1)?;
+        }
+
+        for &(ref name, ref value) in &self.headers {
+            write!(out, "{}: {}\r\n", name, value)?;
+        }
+        write!(out, "Content-Length: {}\r\n", self.body.len())?;
+        write!(out, "\r\n")?;
+        out.write_all(&self.body)?;
+
+        Ok(())
+    }
+}
+
+/// Build a `Response`.
+///
+/// Mirrors the fluent `Builder` used to construct test `Request`s.
+pub struct ResponseBuilder {
+    response: Response,
+}
+
+impl ResponseBuilder {
+    /// Start building a response.
+    pub fn new() -> ResponseBuilder {
+        ResponseBuilder { response: Response::default() }
+    }
+
+    /// Finish building the response.
+    pub fn build(self) -> Response {
+        self.response
+    }
+
+    /// Set the status code and reason phrase.
+    pub fn status(mut self, status: Status) -> ResponseBuilder {
+        self.response.status = Some(status);
+        self.response.location = None;
+        self
+    }
+
+    /// Redirect to `location`.
+    pub fn redirect(mut self, location: &str) -> ResponseBuilder {
+        self.response.location = Some(location.to_string());
+        self.response.status = if location.contains("://") { Some(Status::FOUND) } else { None };
+        self
+    }
+
+    /// Set a response header.
+    ///
+    /// `Content-Type` and `Content-Length` are managed automatically by `body` and should not be
+    /// set here.
+    pub fn header(mut self, name: &str, value: &str) -> ResponseBuilder {
+        self.response.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Append a `Set-Cookie` header.
+    pub fn cookie(mut self, cookie: SetCookie) -> ResponseBuilder {
+        self.response.headers.push(("Set-Cookie".to_string(), cookie.into_header_value()));
+        self
+    }
+
+    /// Set the response body, inferring `Content-Type` from `&str`, raw bytes, or `Content`.
+    pub fn body<T: Into<Body>>(mut self, body: T) -> ResponseBuilder {
+        let body = body.into();
+        if let Some(content_type) = body.content_type {
+            self.response.headers.push(("Content-Type".to_string(), content_type.to_string()));
+        }
+        self.response.body = body.data;
+        self
+    }
+
+    /// Finish building and immediately send the response to stdout.
+    pub fn send(self) -> CgiResult<()> {
+        self.build().send()
+    }
+}